@@ -0,0 +1,28 @@
+//! Domain-separation tag for the KDF that masks OT messages.
+//!
+//! This is **not** an RSA padding-scheme selector, despite this crate's
+//! history of calling it one. The core EGL exchange
+//! (`OTSender::encrypt_messages` / `OTReceiver::decrypt_message`) never
+//! calls into `rsa`'s `PaddingScheme`: it derives `k0`/`k1` via raw modular
+//! exponentiation on an already-blinded domain element, and
+//! `PaddingScheme::{Pkcs1v15Encrypt, Oaep}` both re-encode their input
+//! before exponentiating, which would break the additive blinding
+//! (`v = x_b + k^e`) the whole protocol rests on.
+//!
+//! `KdfDomain` only selects which single byte `kdf::derive_key` mixes into
+//! its SHA-256 expansion as a domain separator. Both variants run the exact
+//! same construction and offer the exact same security property - sender/
+//! receiver privacy rests entirely on RSA hardness plus the additive
+//! blinding, same as the rest of the protocol - so picking one variant over
+//! the other buys nothing cryptographically. It exists purely so sender and
+//! receiver can agree on a tag instead of hardcoding one, and so
+//! `decrypt_message` still fails shut uniformly if they don't; see
+//! `lib.rs` for why this module doesn't attempt real RSA padding. For a
+//! variant where the padding choice is a real `PaddingScheme`, see
+//! `ot_n::RsaPadding`.
+#[derive(Clone, Debug, Copy, PartialEq, Default)]
+pub enum KdfDomain {
+    V1,
+    #[default]
+    V2,
+}
@@ -1,6 +1,45 @@
+//! `OTSender`/`OTReceiver`'s `kdf_domain` field (see [`KdfDomain`]) is
+//! cosmetic domain separation, not a security knob: this crate's EGL
+//! redesign (see `OTSender`'s doc below) already closed the
+//! Bleichenbacher-style padding-oracle gap chunk0-2 originally targeted, by
+//! never calling into RSA's `PaddingScheme` for the base OT at all and by
+//! having `decrypt_message` fail with one uniform `protocol_error`
+//! regardless of cause. Picking [`KdfDomain::V1`] vs [`KdfDomain::V2`]
+//! changes a tag byte, nothing more. A real `PaddingScheme` choice does
+//! exist in this crate - see [`ot_n::RsaPadding`], used by the 1-out-of-N
+//! variant that encrypts directly under RSA.
+//!
+//! **Security note on [`ot_n`]:** `OTSenderN`/`OTReceiverN` (1-out-of-N,
+//! `N > 2`) do **not** carry the sender-privacy guarantee described above.
+//! They predate the EGL redesign and keep the older "one real keypair plus
+//! `N - 1` discarded fake keypairs" construction, which trusts the receiver
+//! to actually discard the fake private keys; nothing stops a malicious
+//! receiver from keeping two real keypairs and recovering two messages.
+//! See the `ot_n` module docs for detail before reaching for it over the
+//! 2-message protocol above.
+
 use anyhow::{anyhow, Result};
+use num_bigint_dig::{BigUint, RandBigInt};
 use rand::rngs::OsRng;
-use rsa::{PaddingScheme, PublicKey, RsaPrivateKey, RsaPublicKey};
+use rsa::{Hash, PaddingScheme, PublicKey, PublicKeyParts, RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+
+pub mod kdf;
+pub mod kdf_domain;
+pub mod ot_ext;
+pub mod ot_n;
+pub mod wire;
+pub mod xor;
+
+use kdf::derive_key;
+use kdf_domain::KdfDomain;
+use xor::mask;
+
+/// A single error returned for every OT handshake failure, regardless of
+/// cause, so a party watching failures can't use them as an oracle.
+pub(crate) fn protocol_error() -> anyhow::Error {
+    anyhow!("oblivious transfer protocol error")
+}
 
 /// Choice enum for 1-out-of-2 OT
 #[derive(Clone, Debug, Copy, PartialEq)]
@@ -26,144 +65,251 @@ impl Choice {
     }
 }
 
-/// Classical RSA-based OT implementation (Even-Goldreich-Lempel)
-/// Uses external RSA library for all cryptographic operations
-#[derive(Clone, Debug)]
+/// Textbook Even-Goldreich-Lempel 1-out-of-2 oblivious transfer.
+///
+/// Unlike the "two public keys" variant, a single RSA keypair lives with the
+/// sender for the whole protocol. The sender also picks two random blinding
+/// constants `x0, x1 \in Z_N`. The receiver blinds its choice bit `b` by
+/// sampling a random `k` and returning `v = x_b + k^e mod N`; because `v`
+/// alone doesn't reveal `b`, the sender can't tell which message the
+/// receiver is about to unmask. The sender recovers `k0 = (v - x0)^d mod N`
+/// and `k1 = (v - x1)^d mod N` and masks each message with `KDF(k_i)`. Only
+/// the receiver's own `k` lets it undo one of the two masks: deriving the
+/// other would require inverting RSA without `d`.
+#[derive(Clone)]
 pub struct OTSender {
     messages: Vec<Vec<u8>>,
+    private_key: RsaPrivateKey,
+    x0: BigUint,
+    x1: BigUint,
+    kdf_domain: KdfDomain,
+    signing_key: Option<RsaPrivateKey>,
 }
 
 pub struct OTReceiver {
     choice: Choice,
-    private_key: Option<RsaPrivateKey>,
-    fake_public_key: Option<RsaPublicKey>,
+    k: Option<BigUint>,
+    v: Option<BigUint>,
+    kdf_domain: KdfDomain,
+    verify_key: Option<RsaPublicKey>,
 }
 
-/// Public keys sent from receiver to sender
+/// Sender's RSA modulus/exponent, blinding constants, and KDF domain tag.
+/// Sent to the receiver before it can blind its choice; the receiver checks
+/// `kdf_domain` matches its own so both sides mask and unmask the same way.
+#[derive(Clone)]
+pub struct SenderParameters {
+    pub n: BigUint,
+    pub e: BigUint,
+    pub x0: BigUint,
+    pub x1: BigUint,
+    pub kdf_domain: KdfDomain,
+}
+
+/// Receiver's blinded choice, sent to the sender. `v` is indistinguishable
+/// from random to anyone who doesn't know `x0`, `x1`, and `d`.
 #[derive(Clone)]
 pub struct ReceiverPublicKeys {
-    pub pk0: RsaPublicKey,
-    pub pk1: RsaPublicKey,
+    pub v: BigUint,
 }
 
-/// Sender's response with encrypted messages
+/// Sender's response: both messages masked with a KDF of their respective
+/// blinding key. The receiver can only remove one of the two masks. When the
+/// sender has a signing key, `signature` binds the transcript (`v` and both
+/// masked messages) so a man-in-the-middle can't swap ciphertexts unnoticed.
 #[derive(Clone)]
 pub struct SenderResponse {
-    pub encrypted_m0: Vec<u8>,
-    pub encrypted_m1: Vec<u8>,
+    pub masked_m0: Vec<u8>,
+    pub masked_m1: Vec<u8>,
+    pub signature: Option<Vec<u8>>,
+}
+
+/// Computes `(a - b) mod n` for unsigned `BigUint`s, which don't support
+/// negative values directly.
+fn sub_mod(a: &BigUint, b: &BigUint, n: &BigUint) -> BigUint {
+    if a >= b {
+        (a - b) % n
+    } else {
+        (n - (b - a) % n) % n
+    }
+}
+
+/// Hashes the transcript a `SenderResponse` signature is made over: `v`
+/// binds the response to this particular exchange, and both masked messages
+/// bind it to their exact bytes.
+fn transcript_digest(v: &BigUint, masked_m0: &[u8], masked_m1: &[u8]) -> Vec<u8> {
+    let mut transcript = v.to_bytes_be();
+    transcript.extend_from_slice(masked_m0);
+    transcript.extend_from_slice(masked_m1);
+    Sha256::digest(transcript).to_vec()
 }
 
 impl OTSender {
-    /// Create a new classical RSA-based OT sender with two messages
+    /// Create a new EGL OT sender with two messages, generating the
+    /// protocol's only RSA keypair and both blinding constants. Defaults to
+    /// [`KdfDomain::V2`]; use [`OTSender::with_kdf_domain`] to pick
+    /// explicitly.
     pub fn new(message0: Vec<u8>, message1: Vec<u8>) -> Result<Self> {
+        Self::with_kdf_domain(message0, message1, KdfDomain::default())
+    }
+
+    /// Same as [`OTSender::new`], with an explicit [`KdfDomain`] tag. Both
+    /// tags are cryptographically equivalent; see the module-level doc for
+    /// why.
+    pub fn with_kdf_domain(
+        message0: Vec<u8>,
+        message1: Vec<u8>,
+        kdf_domain: KdfDomain,
+    ) -> Result<Self> {
+        let mut rng = OsRng;
+
+        let bits = 1024; // Small for demo, but more realistic than our custom implementation
+        let private_key = RsaPrivateKey::new(&mut rng, bits)?;
+        let n = private_key.n().clone();
+        let x0 = rng.gen_biguint_below(&n);
+        let x1 = rng.gen_biguint_below(&n);
+
         Ok(OTSender {
             messages: vec![message0, message1],
+            private_key,
+            x0,
+            x1,
+            kdf_domain,
+            signing_key: None,
         })
     }
 
-    /// Phase 2: Encrypt messages using receiver's public keys with RSA library
+    /// Attaches a long-term RSA signing keypair: every `SenderResponse` this
+    /// sender produces afterwards is signed, binding its transcript so a
+    /// man-in-the-middle can't swap ciphertexts unnoticed.
+    pub fn with_signing_key(mut self, signing_key: RsaPrivateKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// The verification key a receiver needs to check this sender's
+    /// signatures, if one was attached with [`OTSender::with_signing_key`].
+    pub fn verifying_key(&self) -> Option<RsaPublicKey> {
+        self.signing_key.as_ref().map(RsaPublicKey::from)
+    }
+
+    /// Phase 1: Parameters the receiver needs before it can blind its choice.
+    pub fn parameters(&self) -> SenderParameters {
+        SenderParameters {
+            n: self.private_key.n().clone(),
+            e: self.private_key.e().clone(),
+            x0: self.x0.clone(),
+            x1: self.x1.clone(),
+            kdf_domain: self.kdf_domain,
+        }
+    }
+
+    /// Phase 2: Derive both blinding keys from `v`, mask each message with
+    /// its own KDF output, and sign the transcript if a signing key is set.
     pub fn encrypt_messages(&self, receiver_pks: ReceiverPublicKeys) -> Result<SenderResponse> {
-        let mut rng = OsRng;
+        let n = self.private_key.n();
+        let d = self.private_key.d();
+        let v = receiver_pks.v;
 
-        // Encrypt message 0 with pk0 using RSA library
-        let encrypted_m0 = receiver_pks.pk0.encrypt(
-            &mut rng,
-            PaddingScheme::new_pkcs1v15_encrypt(),
+        let k0 = sub_mod(&v, &self.x0, n).modpow(d, n);
+        let k1 = sub_mod(&v, &self.x1, n).modpow(d, n);
+
+        let masked_m0 = mask(
             &self.messages[0],
+            &derive_key(&k0, self.kdf_domain, self.messages[0].len()),
         )?;
-
-        // Encrypt message 1 with pk1 using RSA library
-        let encrypted_m1 = receiver_pks.pk1.encrypt(
-            &mut rng,
-            PaddingScheme::new_pkcs1v15_encrypt(),
+        let masked_m1 = mask(
             &self.messages[1],
+            &derive_key(&k1, self.kdf_domain, self.messages[1].len()),
         )?;
 
+        let signature = match &self.signing_key {
+            Some(signing_key) => {
+                let digest = transcript_digest(&v, &masked_m0, &masked_m1);
+                let padding = PaddingScheme::new_pkcs1v15_sign(Some(Hash::SHA2_256));
+                Some(signing_key.sign(padding, &digest)?)
+            }
+            None => None,
+        };
+
         Ok(SenderResponse {
-            encrypted_m0,
-            encrypted_m1,
+            masked_m0,
+            masked_m1,
+            signature,
         })
     }
 }
 
 impl OTReceiver {
-    /// Create a new classical RSA-based OT receiver with a choice
+    /// Create a new EGL OT receiver with a choice. Defaults to
+    /// [`KdfDomain::V2`]; use [`OTReceiver::with_kdf_domain`] to pick
+    /// explicitly.
     pub fn new(choice: Choice) -> Self {
+        Self::with_kdf_domain(choice, KdfDomain::default())
+    }
+
+    /// Same as [`OTReceiver::new`], with an explicit [`KdfDomain`] tag. Both
+    /// tags are cryptographically equivalent; see the module-level doc for
+    /// why.
+    pub fn with_kdf_domain(choice: Choice, kdf_domain: KdfDomain) -> Self {
         OTReceiver {
             choice,
-            private_key: None,
-            fake_public_key: None,
+            k: None,
+            v: None,
+            kdf_domain,
+            verify_key: None,
         }
     }
 
-    /// Phase 1: Generate RSA key pair and create blinded public keys based on choice
-    /// Uses external RSA library for key generation (blackboxed)
-    ///
-    /// Security: Receiver generates fake public key but immediately discards the private key
-    /// This ensures receiver cannot decrypt messages encrypted with fake public key
-    pub fn generate_public_keys(&mut self) -> Result<ReceiverPublicKeys> {
+    /// Attaches the sender's verification key: `decrypt_message` will then
+    /// reject any response whose transcript signature doesn't check out.
+    pub fn with_verify_key(mut self, verify_key: RsaPublicKey) -> Self {
+        self.verify_key = Some(verify_key);
+        self
+    }
+
+    /// Phase 1: Blind the chosen `x_b` with a fresh random `k`, keeping `k`
+    /// and `v` around for unmasking and signature verification later.
+    pub fn blind_choice(&mut self, params: &SenderParameters) -> Result<ReceiverPublicKeys> {
+        if params.kdf_domain != self.kdf_domain {
+            return Err(protocol_error());
+        }
+
         let mut rng = OsRng;
+        let k = rng.gen_biguint_below(&params.n);
 
-        // Generate real RSA key pair using external library (blackboxed)
-        let bits = 1024; // Small for demo, but more realistic than our custom implementation
-        self.private_key = Some(RsaPrivateKey::new(&mut rng, bits)?);
-        let real_public_key = RsaPublicKey::from(self.private_key.as_ref().unwrap());
-
-        // Generate fake public key - receiver generates random public key but does NOT retain the private key
-        // This ensures receiver cannot decrypt messages encrypted with the fake public key
-        let fake_private_key = RsaPrivateKey::new(&mut rng, bits)?;
-        let fake_public_key = RsaPublicKey::from(&fake_private_key);
-        // Deliberately drop fake_private_key here - receiver must not retain it
-        drop(fake_private_key);
-        self.fake_public_key = Some(fake_public_key);
-
-        // Arrange public keys based on choice
-        // The key insight: receiver puts their real public key in the chosen position
-        // and a fake public key in the other position
-        let (pk0, pk1) = match self.choice {
-            Choice::Zero => {
-                // Real key goes to position 0, fake to position 1
-                (
-                    real_public_key,
-                    self.fake_public_key.as_ref().unwrap().clone(),
-                )
-            }
-            Choice::One => {
-                // Fake goes to position 0, real key to position 1
-                (
-                    self.fake_public_key.as_ref().unwrap().clone(),
-                    real_public_key,
-                )
-            }
+        let x_b = match self.choice {
+            Choice::Zero => &params.x0,
+            Choice::One => &params.x1,
         };
+        let v = (x_b + k.modpow(&params.e, &params.n)) % &params.n;
+        self.k = Some(k);
+        self.v = Some(v.clone());
 
-        Ok(ReceiverPublicKeys { pk0, pk1 })
+        Ok(ReceiverPublicKeys { v })
     }
 
-    /// Phase 2: Decrypt the chosen message using RSA library (blackboxed)
+    /// Phase 2: Verify the transcript signature (if a verify key is set),
+    /// then unmask the chosen message using the `k` picked in phase 1.
     pub fn decrypt_message(&self, response: SenderResponse) -> Result<Vec<u8>> {
-        let private_key = self
-            .private_key
-            .as_ref()
-            .ok_or_else(|| anyhow!("Invalid protocol state: private key not generated"))?;
-
-        let padding = PaddingScheme::new_pkcs1v15_encrypt();
-
-        // Receiver can only decrypt the message encrypted with their real public key
-        let ciphertext = match self.choice {
-            Choice::Zero => {
-                // Real key was pk0, so decrypt encrypted_m0
-                &response.encrypted_m0
-            }
-            Choice::One => {
-                // Real key was pk1, so decrypt encrypted_m1
-                &response.encrypted_m1
-            }
+        let k = self.k.as_ref().ok_or_else(protocol_error)?;
+
+        if let Some(verify_key) = &self.verify_key {
+            let v = self.v.as_ref().ok_or_else(protocol_error)?;
+            let signature = response.signature.as_ref().ok_or_else(protocol_error)?;
+            let digest = transcript_digest(v, &response.masked_m0, &response.masked_m1);
+            let padding = PaddingScheme::new_pkcs1v15_sign(Some(Hash::SHA2_256));
+            verify_key
+                .verify(padding, &digest, signature)
+                .map_err(|_| protocol_error())?;
+        }
+
+        let masked = match self.choice {
+            Choice::Zero => &response.masked_m0,
+            Choice::One => &response.masked_m1,
         };
 
-        // Decrypt using RSA library (blackboxed)
-        let decrypted = private_key.decrypt(padding, ciphertext)?;
-        Ok(decrypted)
+        mask(masked, &derive_key(k, self.kdf_domain, masked.len()))
     }
 }
 
@@ -172,80 +318,204 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_classical_rsa_ot_choice_zero() -> Result<()> {
+    fn test_egl_ot_choice_zero() -> Result<()> {
         let message0 = b"Hello Alice!".to_vec();
         let message1 = b"Hello Bob!!".to_vec();
 
-        // Create sender and receiver
         let sender = OTSender::new(message0.clone(), message1.clone())?;
         let mut receiver = OTReceiver::new(Choice::Zero);
 
-        // Phase 1: Receiver generates and sends public keys using RSA library
-        let public_keys = receiver.generate_public_keys()?;
-
-        // Phase 2: Sender encrypts messages with public keys using RSA library
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
         let response = sender.encrypt_messages(public_keys)?;
-
-        // Phase 2: Receiver decrypts chosen message using RSA library
         let decrypted = receiver.decrypt_message(response)?;
 
-        // Verify we got the correct message
         assert_eq!(decrypted, message0);
         Ok(())
     }
 
     #[test]
-    fn test_classical_rsa_ot_choice_one() -> Result<()> {
+    fn test_egl_ot_choice_one() -> Result<()> {
         let message0 = b"Secret Zero".to_vec();
         let message1 = b"Secret One!".to_vec();
 
         let sender = OTSender::new(message0.clone(), message1.clone())?;
         let mut receiver = OTReceiver::new(Choice::One);
 
-        let public_keys = receiver.generate_public_keys()?;
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
         let response = sender.encrypt_messages(public_keys)?;
         let decrypted = receiver.decrypt_message(response)?;
 
-        // Verify we got the correct message
         assert_eq!(decrypted, message1);
         Ok(())
     }
 
     #[test]
-    fn test_sender_cannot_distinguish_keys() -> Result<()> {
-        // This test verifies that from sender's perspective,
-        // both public keys look valid (sender can't tell which is real)
+    fn test_egl_ot_message_longer_than_kdf_block_is_fully_masked() -> Result<()> {
+        // Longer than both the single-block (32-byte) and stretched
+        // (64-byte) KDF output, so masking must stretch the key rather than
+        // zero-extend it; otherwise bytes past the key's length would come
+        // out of `encrypt_messages` unchanged.
+        let message0 = vec![0x11u8; 200];
+        let message1 = vec![0x22u8; 200];
 
+        let sender = OTSender::new(message0.clone(), message1.clone())?;
         let mut receiver = OTReceiver::new(Choice::Zero);
-        let public_keys = receiver.generate_public_keys()?;
 
-        // Both keys should be usable for encryption (though one won't be decryptable)
-        let test_msg = b"test message";
-        let sender = OTSender::new(test_msg.to_vec(), test_msg.to_vec())?;
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
+        let response = sender.encrypt_messages(public_keys)?;
 
-        // Both encryptions should succeed (this demonstrates sender can't distinguish)
-        let result = sender.encrypt_messages(public_keys);
-        assert!(result.is_ok());
+        assert_ne!(response.masked_m0, message0);
+        assert_ne!(
+            response.masked_m0[32..],
+            message0[32..],
+            "bytes past the first KDF block must still be masked"
+        );
 
+        let decrypted = receiver.decrypt_message(response)?;
+        assert_eq!(decrypted, message0);
         Ok(())
     }
 
     #[test]
-    fn test_cannot_decrypt_wrong_message() -> Result<()> {
-        let message0 = b"Should not get this".to_vec();
-        let message1 = b"Should get this one".to_vec();
+    fn test_receiver_cannot_recover_other_message() -> Result<()> {
+        let message0 = b"Should get this one".to_vec();
+        let message1 = b"Should not get this".to_vec();
 
         let sender = OTSender::new(message0.clone(), message1.clone())?;
-        let mut receiver = OTReceiver::new(Choice::One);
+        let mut receiver = OTReceiver::new(Choice::Zero);
 
-        let public_keys = receiver.generate_public_keys()?;
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
         let response = sender.encrypt_messages(public_keys)?;
 
-        // Receiver should get message1, not message0
+        // A cheating receiver only knows `k` for its own choice; trying to
+        // unmask the other branch with it yields noise, not the message,
+        // because that branch was masked with KDF((v - x1)^d), not KDF(k).
+        let forged = mask(
+            &response.masked_m1,
+            &derive_key(
+                receiver.k.as_ref().unwrap(),
+                receiver.kdf_domain,
+                response.masked_m1.len(),
+            ),
+        )?;
+        assert_ne!(forged, message1);
+
         let decrypted = receiver.decrypt_message(response)?;
-        assert_eq!(decrypted, message1);
-        assert_ne!(decrypted, message0);
+        assert_eq!(decrypted, message0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_before_blinding_fails() -> Result<()> {
+        let message0 = b"m0".to_vec();
+        let message1 = b"m1".to_vec();
+
+        let sender = OTSender::new(message0, message1)?;
+        let params = sender.parameters();
+
+        let mut other_receiver = OTReceiver::new(Choice::Zero);
+        let public_keys = other_receiver.blind_choice(&params)?;
+        let response = sender.encrypt_messages(public_keys)?;
+
+        // This receiver never blinded its choice, so it has no `k` to unmask with.
+        let receiver = OTReceiver::new(Choice::Zero);
+        assert!(receiver.decrypt_message(response).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_mismatched_kdf_domain_rejected() -> Result<()> {
+        let sender = OTSender::with_kdf_domain(b"m0".to_vec(), b"m1".to_vec(), KdfDomain::V2)?;
+        let mut receiver = OTReceiver::with_kdf_domain(Choice::Zero, KdfDomain::V1);
+
+        let params = sender.parameters();
+        assert!(receiver.blind_choice(&params).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_response_verifies_and_decrypts() -> Result<()> {
+        let message0 = b"Hello Alice!".to_vec();
+        let message1 = b"Hello Bob!!".to_vec();
+
+        let signing_key = RsaPrivateKey::new(&mut OsRng, 1024)?;
+        let sender = OTSender::new(message0.clone(), message1.clone())?.with_signing_key(signing_key);
+        let verify_key = sender.verifying_key().unwrap();
+        let mut receiver = OTReceiver::new(Choice::Zero).with_verify_key(verify_key);
+
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
+        let response = sender.encrypt_messages(public_keys)?;
+        let decrypted = receiver.decrypt_message(response)?;
+
+        assert_eq!(decrypted, message0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tampered_masked_message_fails_verification() -> Result<()> {
+        let message0 = b"Hello Alice!".to_vec();
+        let message1 = b"Hello Bob!!".to_vec();
+
+        let signing_key = RsaPrivateKey::new(&mut OsRng, 1024)?;
+        let sender = OTSender::new(message0, message1)?.with_signing_key(signing_key);
+        let verify_key = sender.verifying_key().unwrap();
+        let mut receiver = OTReceiver::new(Choice::Zero).with_verify_key(verify_key);
+
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
+        let mut response = sender.encrypt_messages(public_keys)?;
+
+        // A man-in-the-middle flips a bit in the masked ciphertext after
+        // the sender signed it; the signature no longer matches.
+        response.masked_m0[0] ^= 0x01;
+
+        assert!(receiver.decrypt_message(response).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tampered_signature_fails_verification() -> Result<()> {
+        let message0 = b"Hello Alice!".to_vec();
+        let message1 = b"Hello Bob!!".to_vec();
+
+        let signing_key = RsaPrivateKey::new(&mut OsRng, 1024)?;
+        let sender = OTSender::new(message0, message1)?.with_signing_key(signing_key);
+        let verify_key = sender.verifying_key().unwrap();
+        let mut receiver = OTReceiver::new(Choice::Zero).with_verify_key(verify_key);
+
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
+        let mut response = sender.encrypt_messages(public_keys)?;
+
+        let signature = response.signature.as_mut().expect("response is signed");
+        signature[0] ^= 0x01;
+
+        assert!(receiver.decrypt_message(response).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsigned_response_rejected_when_verify_key_set() -> Result<()> {
+        let message0 = b"Hello Alice!".to_vec();
+        let message1 = b"Hello Bob!!".to_vec();
+
+        // Sender has no signing key, so its responses carry no signature,
+        // but the receiver still expects one.
+        let sender = OTSender::new(message0, message1)?;
+        let signing_key = RsaPrivateKey::new(&mut OsRng, 1024)?;
+        let verify_key = RsaPublicKey::from(&signing_key);
+        let mut receiver = OTReceiver::new(Choice::Zero).with_verify_key(verify_key);
+
+        let params = sender.parameters();
+        let public_keys = receiver.blind_choice(&params)?;
+        let response = sender.encrypt_messages(public_keys)?;
 
+        assert!(receiver.decrypt_message(response).is_err());
         Ok(())
     }
 }
@@ -0,0 +1,289 @@
+//! IKNP OT extension: turns `K` base OTs (the RSA [`OTSender`]/[`OTReceiver`])
+//! into `m` cheap 1-out-of-2 OTs using only symmetric operations (`xor` and
+//! SHA-256), so a protocol needing thousands of OTs doesn't need thousands
+//! of RSA operations.
+//!
+//! The extended-OT receiver holds selection bits `r` and samples two `m×K`
+//! bit matrices `T0`, `T1` with the row constraint `t0_i ⊕ t1_i = r_i · 1^K`.
+//! For each of the `K` columns, a base OT runs with roles reversed: the
+//! extended-OT sender plays the base-OT *receiver* with a random `K`-bit
+//! choice string `s`, obtaining column `t_{s_j}^j`; the extended-OT receiver
+//! plays the base-OT *sender*, offering `(T0 column j, T1 column j)`. Once
+//! all `K` columns have been exchanged, the sender assembles matrix `Q` with
+//! `q_i = t0_i ⊕ (r_i · s)` and masks each message pair with a
+//! correlation-robust hash of `q_i` (and `q_i ⊕ s`); the receiver undoes the
+//! one mask it can, using its own `t0_i`.
+//!
+//! Bits are represented one-per-byte (`0u8`/`1u8`) rather than packed, to
+//! keep this demo implementation simple; `K` base OTs and `m` cheap
+//! symmetric operations are exactly what the protocol calls for either way.
+
+use anyhow::{anyhow, Result};
+use rand::rngs::OsRng;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::xor::mask;
+use crate::{Choice, OTReceiver, OTSender};
+
+/// Number of base OTs run to extend into `m` derived OTs; the standard IKNP
+/// security parameter.
+pub const K: usize = 128;
+
+/// The sender's masked outputs for one extended OT, analogous to
+/// [`crate::SenderResponse`] but for a single row of the IKNP extension.
+#[derive(Clone)]
+pub struct ExtOutput {
+    pub y0: Vec<u8>,
+    pub y1: Vec<u8>,
+}
+
+/// Hashes `(i, input)` with SHA-256, counter-mode-stretched to `len` bytes.
+/// Used in place of a dedicated correlation-robust hash, the same
+/// substitution `kdf` makes for the base OT's KDF. `len` must match the
+/// message `xor::mask` will apply the output to: like `kdf::derive_key`,
+/// this never pads or truncates on `mask`'s behalf, so a fixed one-block
+/// hash would leave every byte past 32 unmasked for longer messages.
+fn correlation_hash(i: usize, input: &[u8], len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len + Sha256::output_size());
+    let mut counter: u32 = 0;
+    while out.len() < len {
+        let mut block = (i as u64).to_be_bytes().to_vec();
+        block.extend_from_slice(input);
+        block.extend_from_slice(&counter.to_be_bytes());
+        out.extend_from_slice(&Sha256::digest(block));
+        counter += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+fn random_bits(rng: &mut OsRng, len: usize) -> Vec<u8> {
+    (0..len).map(|_| rng.gen::<bool>() as u8).collect()
+}
+
+/// Receiver-side state for the IKNP OT extension: the `m` selection bits
+/// `r` and the `T0` bit-matrix (`m` rows of `K` bits each) sampled to
+/// satisfy the row constraint `t0_i ⊕ t1_i = r_i · 1^K`.
+pub struct OTExtReceiver {
+    r: Vec<u8>,
+    t0: Vec<Vec<u8>>,
+}
+
+impl OTExtReceiver {
+    /// Samples `T0` at random for the given selection bits (one per
+    /// extended OT the receiver wants to run).
+    pub fn new(choices: Vec<u8>) -> Self {
+        let mut rng = OsRng;
+        let t0 = choices.iter().map(|_| random_bits(&mut rng, K)).collect();
+        OTExtReceiver { r: choices, t0 }
+    }
+
+    /// Phase 1: Builds the `K` base-OT senders, one per column, offering
+    /// `(T0 column j, T1 column j)`. The caller drives each base OT's
+    /// handshake against the matching [`OTExtSender`] base-OT receiver.
+    pub fn base_senders(&self) -> Result<Vec<OTSender>> {
+        (0..K)
+            .map(|j| {
+                let col0: Vec<u8> = self.t0.iter().map(|row| row[j]).collect();
+                let col1: Vec<u8> = self
+                    .t0
+                    .iter()
+                    .zip(self.r.iter())
+                    .map(|(row, &r_i)| row[j] ^ r_i)
+                    .collect();
+                OTSender::new(col0, col1)
+            })
+            .collect()
+    }
+
+    /// Phase 2: Recovers `m_{r_i}` for every row from the sender's masked
+    /// outputs, using the `t0_i` sampled in [`OTExtReceiver::new`].
+    pub fn recover(&self, outputs: &[ExtOutput]) -> Result<Vec<Vec<u8>>> {
+        if outputs.len() != self.r.len() {
+            return Err(anyhow!(
+                "expected {} outputs, got {}",
+                self.r.len(),
+                outputs.len()
+            ));
+        }
+
+        outputs
+            .iter()
+            .zip(self.r.iter())
+            .enumerate()
+            .map(|(i, (output, &r_i))| {
+                let y = if r_i == 0 { &output.y0 } else { &output.y1 };
+                let hash = correlation_hash(i, &self.t0[i], y.len());
+                mask(y, &hash)
+            })
+            .collect()
+    }
+}
+
+/// Sender-side state for the IKNP OT extension: the random `K`-bit choice
+/// string `s` used to run the `K` base OTs in the reversed role.
+pub struct OTExtSender {
+    s: Vec<u8>,
+}
+
+impl OTExtSender {
+    /// Samples the `K`-bit choice string `s`.
+    pub fn new() -> Self {
+        let mut rng = OsRng;
+        OTExtSender {
+            s: random_bits(&mut rng, K),
+        }
+    }
+
+    /// Phase 1: The base-OT receiver for column `j`, choosing `s_j`. The
+    /// caller drives each base OT's handshake against the matching
+    /// [`OTExtReceiver`] base-OT sender.
+    pub fn base_receiver(&self, j: usize) -> Result<OTReceiver> {
+        let choice = Choice::from_bit(self.s[j])?;
+        Ok(OTReceiver::new(choice))
+    }
+
+    /// Phase 2: Assembles `Q` from the `K` base-OT columns `t_{s_j}^j` and
+    /// masks each message pair with a correlation-robust hash of `q_i`
+    /// (and `q_i ⊕ s`, for the other message).
+    pub fn encrypt_messages(
+        &self,
+        columns: Vec<Vec<u8>>,
+        messages: &[(Vec<u8>, Vec<u8>)],
+    ) -> Result<Vec<ExtOutput>> {
+        if columns.len() != K {
+            return Err(anyhow!("expected {} base OT columns, got {}", K, columns.len()));
+        }
+        let m = messages.len();
+        if columns.iter().any(|col| col.len() != m) {
+            return Err(anyhow!(
+                "base OT columns don't match the number of messages"
+            ));
+        }
+
+        messages
+            .iter()
+            .enumerate()
+            .map(|(i, (m0, m1))| {
+                let q_i: Vec<u8> = columns.iter().map(|col| col[i]).collect();
+                let q_i_xor_s: Vec<u8> = q_i.iter().zip(self.s.iter()).map(|(a, b)| a ^ b).collect();
+
+                Ok(ExtOutput {
+                    y0: mask(m0, &correlation_hash(i, &q_i, m0.len()))?,
+                    y1: mask(m1, &correlation_hash(i, &q_i_xor_s, m1.len()))?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for OTExtSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs all `K` base OTs between `receiver` and `sender`, returning the
+    /// columns the extended-OT sender obtained (`t_{s_j}^j` for each `j`).
+    fn run_base_ots(ext_receiver: &OTExtReceiver, ext_sender: &OTExtSender) -> Result<Vec<Vec<u8>>> {
+        let base_senders = ext_receiver.base_senders()?;
+        base_senders
+            .iter()
+            .enumerate()
+            .map(|(j, base_sender)| {
+                let mut base_receiver = ext_sender.base_receiver(j)?;
+                let params = base_sender.parameters();
+                let public_keys = base_receiver.blind_choice(&params)?;
+                let response = base_sender.encrypt_messages(public_keys)?;
+                base_receiver.decrypt_message(response)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ot_extension_round_trip() -> Result<()> {
+        let choices = vec![0u8, 1, 1, 0, 1, 0, 0, 1, 1, 1];
+        let messages: Vec<(Vec<u8>, Vec<u8>)> = (0..choices.len())
+            .map(|i| (format!("zero-{i}").into_bytes(), format!("one-{i}").into_bytes()))
+            .collect();
+
+        let ext_receiver = OTExtReceiver::new(choices.clone());
+        let ext_sender = OTExtSender::new();
+
+        let columns = run_base_ots(&ext_receiver, &ext_sender)?;
+        let outputs = ext_sender.encrypt_messages(columns, &messages)?;
+        let results = ext_receiver.recover(&outputs)?;
+
+        for (i, &bit) in choices.iter().enumerate() {
+            let expected = if bit == 0 { &messages[i].0 } else { &messages[i].1 };
+            assert_eq!(&results[i], expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ot_extension_long_messages_do_not_leak_plaintext() -> Result<()> {
+        // Longer than a single SHA-256 block, so masking must stretch the
+        // correlation hash rather than leave bytes past 32 unmasked.
+        let choices = vec![0u8, 1, 0, 1];
+        let messages: Vec<(Vec<u8>, Vec<u8>)> = choices
+            .iter()
+            .map(|_| (vec![0x11u8; 200], vec![0x22u8; 200]))
+            .collect();
+
+        let ext_receiver = OTExtReceiver::new(choices.clone());
+        let ext_sender = OTExtSender::new();
+
+        let columns = run_base_ots(&ext_receiver, &ext_sender)?;
+        let outputs = ext_sender.encrypt_messages(columns, &messages)?;
+
+        for (output, (m0, m1)) in outputs.iter().zip(messages.iter()) {
+            assert_ne!(&output.y0, m0);
+            assert_ne!(&output.y1, m1);
+            assert_ne!(
+                output.y0[32..],
+                m0[32..],
+                "bytes past the first hash block must still be masked"
+            );
+            assert_ne!(
+                output.y1[32..],
+                m1[32..],
+                "bytes past the first hash block must still be masked"
+            );
+        }
+
+        let results = ext_receiver.recover(&outputs)?;
+        for (i, &bit) in choices.iter().enumerate() {
+            let expected = if bit == 0 { &messages[i].0 } else { &messages[i].1 };
+            assert_eq!(&results[i], expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ot_extension_many_transfers() -> Result<()> {
+        let mut rng = OsRng;
+        let choices: Vec<u8> = (0..2000).map(|_| rng.gen::<bool>() as u8).collect();
+        let messages: Vec<(Vec<u8>, Vec<u8>)> = (0..choices.len())
+            .map(|i| (format!("m0-{i}").into_bytes(), format!("m1-{i}").into_bytes()))
+            .collect();
+
+        let ext_receiver = OTExtReceiver::new(choices.clone());
+        let ext_sender = OTExtSender::new();
+
+        let columns = run_base_ots(&ext_receiver, &ext_sender)?;
+        let outputs = ext_sender.encrypt_messages(columns, &messages)?;
+        let results = ext_receiver.recover(&outputs)?;
+
+        for (i, &bit) in choices.iter().enumerate() {
+            let expected = if bit == 0 { &messages[i].0 } else { &messages[i].1 };
+            assert_eq!(&results[i], expected);
+        }
+        Ok(())
+    }
+}
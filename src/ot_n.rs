@@ -0,0 +1,290 @@
+//! 1-out-of-N oblivious transfer: generalizes the crate's 1-out-of-2
+//! [`Choice`]/[`OTSender`]/[`OTReceiver`] to an arbitrary number of
+//! messages.
+//!
+//! This variant predates the single-keypair EGL redesign ([`OTSender`]
+//! proper) and keeps the original "one real keypair, `N - 1` discarded fake
+//! keypairs" construction, since EGL's additive blinding is specific to two
+//! alternatives. The receiver generates one real RSA keypair, places its
+//! public key at the chosen index, fills every other index with a freshly
+//! generated public key whose private key it discards, and the sender
+//! encrypts message `i` under public key `i`. A well-behaved receiver can
+//! therefore only decrypt the message at its chosen index; unlike EGL this
+//! still relies on the receiver actually discarding the fake private keys,
+//! the same trust assumption the crate's original implementation made.
+//!
+//! **Security: weaker than [`OTSender`]/[`OTReceiver`].** `encrypt_messages`
+//! only checks that the `N` public-key moduli are pairwise distinct; it does
+//! nothing to stop a malicious receiver from generating two *real* keypairs
+//! (distinct moduli, both private keys retained) and placing them at two
+//! different indices, recovering two messages instead of one - the exact
+//! attack class chunk0-1's EGL redesign eliminated for the 2-message case.
+//! Do not reach for `OTSenderN`/`OTReceiverN` expecting EGL's sender-privacy
+//! guarantee; use [`OTSender`]/[`OTReceiver`] (`N = 2`) unless more than two
+//! alternatives are genuinely needed and the receiver's implementation can
+//! be trusted.
+//!
+//! [`Choice`]: crate::Choice
+//! [`OTSender`]: crate::OTSender
+//! [`OTReceiver`]: crate::OTReceiver
+
+use anyhow::{anyhow, Result};
+use rand::rngs::OsRng;
+use rsa::{PaddingScheme, PublicKey, PublicKeyParts, RsaPrivateKey, RsaPublicKey};
+use sha2::Sha256;
+
+use crate::protocol_error;
+use crate::Choice;
+
+/// Selection of one of `N` alternatives for 1-out-of-N oblivious transfer.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct IndexChoice(pub usize);
+
+impl IndexChoice {
+    /// Builds a choice, checking `index` is in range for `n` alternatives.
+    pub fn new(index: usize, n: usize) -> Result<Self> {
+        if index >= n {
+            return Err(anyhow!("choice index {} out of range for N={}", index, n));
+        }
+        Ok(IndexChoice(index))
+    }
+}
+
+impl From<Choice> for IndexChoice {
+    /// The existing 2-message API is just the `N = 2` case of this one.
+    fn from(choice: Choice) -> Self {
+        IndexChoice(choice.to_bit() as usize)
+    }
+}
+
+/// Real RSA `PaddingScheme` used to encrypt each message directly under the
+/// receiver's public key. Unlike [`crate::KdfDomain`] (a cosmetic tag for
+/// the EGL protocol's KDF), this genuinely selects between two different
+/// `rsa` padding schemes, since `OTSenderN` encrypts with `RsaPublicKey`
+/// directly rather than deriving a KDF mask from a blinded domain element.
+#[derive(Clone, Debug, Copy, PartialEq, Default)]
+pub enum RsaPadding {
+    Pkcs1v15,
+    #[default]
+    OaepSha256,
+}
+
+fn padding_scheme(padding: RsaPadding) -> PaddingScheme {
+    match padding {
+        RsaPadding::Pkcs1v15 => PaddingScheme::new_pkcs1v15_encrypt(),
+        RsaPadding::OaepSha256 => PaddingScheme::new_oaep::<Sha256>(),
+    }
+}
+
+pub struct OTSenderN {
+    messages: Vec<Vec<u8>>,
+    padding: RsaPadding,
+}
+
+pub struct OTReceiverN {
+    choice: IndexChoice,
+    n: usize,
+    private_key: Option<RsaPrivateKey>,
+    padding: RsaPadding,
+}
+
+/// Receiver's `N` public keys: the real one at the chosen index, `N - 1`
+/// fake ones elsewhere.
+#[derive(Clone)]
+pub struct ReceiverPublicKeysN {
+    pub public_keys: Vec<RsaPublicKey>,
+}
+
+/// Sender's response: message `i` encrypted under public key `i`.
+#[derive(Clone)]
+pub struct SenderResponseN {
+    pub encrypted: Vec<Vec<u8>>,
+}
+
+impl OTSenderN {
+    /// Creates a new 1-out-of-N OT sender holding `messages`. Defaults to
+    /// OAEP; use [`OTSenderN::with_padding`] to pick explicitly.
+    pub fn new(messages: Vec<Vec<u8>>) -> Result<Self> {
+        Self::with_padding(messages, RsaPadding::default())
+    }
+
+    /// Same as [`OTSenderN::new`], with an explicit [`RsaPadding`] scheme.
+    pub fn with_padding(messages: Vec<Vec<u8>>, padding: RsaPadding) -> Result<Self> {
+        if messages.is_empty() {
+            return Err(anyhow!("OTSenderN needs at least one message"));
+        }
+        Ok(OTSenderN { messages, padding })
+    }
+
+    /// Mirrors the top-level [`crate::OTSender::new`] API for callers that
+    /// only need two alternatives.
+    pub fn new_pair(message0: Vec<u8>, message1: Vec<u8>) -> Result<Self> {
+        Self::new(vec![message0, message1])
+    }
+
+    /// Phase 2: Encrypt message `i` under the receiver's public key `i`.
+    ///
+    /// Rejects mismatched key/message counts and non-distinct public-key
+    /// moduli, but - see the module-level security note - cannot detect a
+    /// receiver that retained more than one real private key.
+    pub fn encrypt_messages(&self, receiver_pks: ReceiverPublicKeysN) -> Result<SenderResponseN> {
+        let public_keys = receiver_pks.public_keys;
+        if public_keys.len() != self.messages.len() {
+            return Err(anyhow!(
+                "expected {} public keys, got {}",
+                self.messages.len(),
+                public_keys.len()
+            ));
+        }
+        for i in 0..public_keys.len() {
+            for j in (i + 1)..public_keys.len() {
+                if public_keys[i].n() == public_keys[j].n() {
+                    return Err(anyhow!("public keys at {} and {} are not distinct", i, j));
+                }
+            }
+        }
+
+        let mut rng = OsRng;
+        let encrypted = self
+            .messages
+            .iter()
+            .zip(public_keys.iter())
+            .map(|(message, public_key)| {
+                public_key
+                    .encrypt(&mut rng, padding_scheme(self.padding), message)
+                    .map_err(anyhow::Error::from)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SenderResponseN { encrypted })
+    }
+}
+
+impl OTReceiverN {
+    /// Creates a new 1-out-of-N OT receiver choosing index `choice.0` among
+    /// `n` alternatives. Defaults to OAEP; use [`OTReceiverN::with_padding`]
+    /// to pick explicitly.
+    pub fn new(choice: IndexChoice, n: usize) -> Result<Self> {
+        Self::with_padding(choice, n, RsaPadding::default())
+    }
+
+    /// Same as [`OTReceiverN::new`], with an explicit [`RsaPadding`] scheme.
+    pub fn with_padding(choice: IndexChoice, n: usize, padding: RsaPadding) -> Result<Self> {
+        if choice.0 >= n {
+            return Err(anyhow!("choice index {} out of range for N={}", choice.0, n));
+        }
+        Ok(OTReceiverN {
+            choice,
+            n,
+            private_key: None,
+            padding,
+        })
+    }
+
+    /// Phase 1: Generate a real RSA keypair for the chosen index, and a
+    /// fake public key (discarding its private key) for every other index.
+    pub fn generate_public_keys(&mut self) -> Result<ReceiverPublicKeysN> {
+        let mut rng = OsRng;
+        let bits = 1024; // Small for demo, but more realistic than our custom implementation
+
+        let real_private_key = RsaPrivateKey::new(&mut rng, bits)?;
+        let real_public_key = RsaPublicKey::from(&real_private_key);
+        self.private_key = Some(real_private_key);
+
+        let public_keys = (0..self.n)
+            .map(|i| {
+                if i == self.choice.0 {
+                    Ok(real_public_key.clone())
+                } else {
+                    // Deliberately drop the fake private key here - receiver
+                    // must not retain it.
+                    let fake_private_key = RsaPrivateKey::new(&mut rng, bits)?;
+                    Ok(RsaPublicKey::from(&fake_private_key))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ReceiverPublicKeysN { public_keys })
+    }
+
+    /// Phase 2: Decrypt the message at the chosen index. A padding/length
+    /// failure returns the same uniform `protocol_error` as the rest of the
+    /// crate, rather than a distinguishable error variant.
+    pub fn decrypt_message(&self, response: SenderResponseN) -> Result<Vec<u8>> {
+        let private_key = self.private_key.as_ref().ok_or_else(protocol_error)?;
+        let ciphertext = response
+            .encrypted
+            .get(self.choice.0)
+            .ok_or_else(protocol_error)?;
+
+        private_key
+            .decrypt(padding_scheme(self.padding), ciphertext)
+            .map_err(|_| protocol_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ot_n_only_chosen_index_decrypts() -> Result<()> {
+        let messages = vec![
+            b"alpha".to_vec(),
+            b"bravo".to_vec(),
+            b"charlie".to_vec(),
+            b"delta".to_vec(),
+        ];
+        let n = messages.len();
+
+        for index in 0..n {
+            let sender = OTSenderN::new(messages.clone())?;
+            let mut receiver = OTReceiverN::new(IndexChoice::new(index, n)?, n)?;
+
+            let public_keys = receiver.generate_public_keys()?;
+            let response = sender.encrypt_messages(public_keys)?;
+            let decrypted = receiver.decrypt_message(response)?;
+
+            assert_eq!(decrypted, messages[index]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ot_n_rejects_out_of_range_choice() {
+        assert!(IndexChoice::new(3, 3).is_err());
+        assert!(OTReceiverN::new(IndexChoice(3), 3).is_err());
+    }
+
+    #[test]
+    fn test_ot_n_rejects_message_key_count_mismatch() -> Result<()> {
+        let sender = OTSenderN::new(vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()])?;
+        let mut receiver = OTReceiverN::new(IndexChoice::new(0, 2)?, 2)?;
+        let public_keys = receiver.generate_public_keys()?;
+
+        assert!(sender.encrypt_messages(public_keys).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ot_n_rejects_duplicate_public_keys() -> Result<()> {
+        let sender = OTSenderN::new(vec![b"one".to_vec(), b"two".to_vec()])?;
+        let mut receiver = OTReceiverN::new(IndexChoice::new(0, 2)?, 2)?;
+        let public_keys = receiver.generate_public_keys()?;
+        let duplicated = ReceiverPublicKeysN {
+            public_keys: vec![
+                public_keys.public_keys[0].clone(),
+                public_keys.public_keys[0].clone(),
+            ],
+        };
+
+        assert!(sender.encrypt_messages(duplicated).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_choice_from_choice() {
+        assert_eq!(IndexChoice::from(Choice::Zero), IndexChoice(0));
+        assert_eq!(IndexChoice::from(Choice::One), IndexChoice(1));
+    }
+}
@@ -0,0 +1,243 @@
+//! Canonical byte encoding for the protocol's wire messages.
+//!
+//! Every message is a flat sequence of length-prefixed fields (a 4-byte
+//! big-endian length followed by that many bytes), so `SenderParameters`,
+//! `ReceiverPublicKeys`, and `SenderResponse` round-trip losslessly when the
+//! two parties only exchange bytes over a socket. The RSA modulus/exponent
+//! inside `SenderParameters` are additionally wrapped in PKCS#8/SPKI DER via
+//! `to_public_key_der`/`from_public_key_der`, the standard encoding for an
+//! RSA public key on the wire.
+
+use anyhow::{anyhow, Result};
+use num_bigint_dig::BigUint;
+use rsa::pkcs8::{DecodePublicKey, EncodePublicKey};
+use rsa::{PublicKeyParts, RsaPublicKey};
+
+use crate::kdf_domain::KdfDomain;
+use crate::{ReceiverPublicKeys, SenderParameters, SenderResponse};
+
+fn write_field(out: &mut Vec<u8>, field: &[u8]) {
+    out.extend_from_slice(&(field.len() as u32).to_be_bytes());
+    out.extend_from_slice(field);
+}
+
+fn read_field(bytes: &[u8], offset: &mut usize) -> Result<Vec<u8>> {
+    let len_bytes = bytes
+        .get(*offset..*offset + 4)
+        .ok_or_else(|| anyhow!("truncated wire message"))?;
+    let len = u32::from_be_bytes(len_bytes.try_into().unwrap()) as usize;
+    *offset += 4;
+
+    let field = bytes
+        .get(*offset..*offset + len)
+        .ok_or_else(|| anyhow!("truncated wire message"))?
+        .to_vec();
+    *offset += len;
+
+    Ok(field)
+}
+
+fn kdf_domain_to_byte(kdf_domain: KdfDomain) -> u8 {
+    match kdf_domain {
+        KdfDomain::V1 => 0,
+        KdfDomain::V2 => 1,
+    }
+}
+
+fn kdf_domain_from_byte(byte: u8) -> Result<KdfDomain> {
+    match byte {
+        0 => Ok(KdfDomain::V1),
+        1 => Ok(KdfDomain::V2),
+        other => Err(anyhow!("unknown KDF domain tag byte: {}", other)),
+    }
+}
+
+impl SenderParameters {
+    /// Encodes these parameters as length-prefixed fields, with the RSA
+    /// public key as PKCS#8/SPKI DER.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let public_key = RsaPublicKey::new(self.n.clone(), self.e.clone())?;
+        let key_der = public_key.to_public_key_der()?;
+
+        let mut out = Vec::new();
+        write_field(&mut out, key_der.as_ref());
+        write_field(&mut out, &self.x0.to_bytes_be());
+        write_field(&mut out, &self.x1.to_bytes_be());
+        out.push(kdf_domain_to_byte(self.kdf_domain));
+        Ok(out)
+    }
+
+    /// Decodes parameters previously encoded with [`SenderParameters::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut offset = 0;
+        let key_der = read_field(bytes, &mut offset)?;
+        let x0 = read_field(bytes, &mut offset)?;
+        let x1 = read_field(bytes, &mut offset)?;
+        let kdf_domain_byte = *bytes
+            .get(offset)
+            .ok_or_else(|| anyhow!("truncated wire message"))?;
+
+        let public_key = RsaPublicKey::from_public_key_der(&key_der)?;
+
+        Ok(SenderParameters {
+            n: public_key.n().clone(),
+            e: public_key.e().clone(),
+            x0: BigUint::from_bytes_be(&x0),
+            x1: BigUint::from_bytes_be(&x1),
+            kdf_domain: kdf_domain_from_byte(kdf_domain_byte)?,
+        })
+    }
+}
+
+impl ReceiverPublicKeys {
+    /// Encodes the blinded choice `v` as a single length-prefixed field.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_field(&mut out, &self.v.to_bytes_be());
+        out
+    }
+
+    /// Decodes a blinded choice previously encoded with
+    /// [`ReceiverPublicKeys::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut offset = 0;
+        let v = read_field(bytes, &mut offset)?;
+        Ok(ReceiverPublicKeys {
+            v: BigUint::from_bytes_be(&v),
+        })
+    }
+}
+
+impl SenderResponse {
+    /// Encodes both masked messages, and the transcript signature if
+    /// present, as length-prefixed fields.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_field(&mut out, &self.masked_m0);
+        write_field(&mut out, &self.masked_m1);
+        match &self.signature {
+            Some(signature) => {
+                out.push(1);
+                write_field(&mut out, signature);
+            }
+            None => out.push(0),
+        }
+        out
+    }
+
+    /// Decodes a response previously encoded with [`SenderResponse::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut offset = 0;
+        let masked_m0 = read_field(bytes, &mut offset)?;
+        let masked_m1 = read_field(bytes, &mut offset)?;
+        let has_signature = *bytes
+            .get(offset)
+            .ok_or_else(|| anyhow!("truncated wire message"))?;
+        offset += 1;
+        let signature = match has_signature {
+            0 => None,
+            1 => Some(read_field(bytes, &mut offset)?),
+            other => return Err(anyhow!("unknown signature presence byte: {}", other)),
+        };
+
+        Ok(SenderResponse {
+            masked_m0,
+            masked_m1,
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Choice, OTReceiver, OTSender};
+    use rsa::RsaPrivateKey;
+
+    #[test]
+    fn test_sender_parameters_round_trip() -> Result<()> {
+        let sender = OTSender::new(b"m0".to_vec(), b"m1".to_vec())?;
+        let params = sender.parameters();
+
+        let bytes = params.to_bytes()?;
+        let decoded = SenderParameters::from_bytes(&bytes)?;
+
+        assert_eq!(decoded.n, params.n);
+        assert_eq!(decoded.e, params.e);
+        assert_eq!(decoded.x0, params.x0);
+        assert_eq!(decoded.x1, params.x1);
+        assert_eq!(decoded.kdf_domain, params.kdf_domain);
+        Ok(())
+    }
+
+    #[test]
+    fn test_receiver_public_keys_round_trip() -> Result<()> {
+        let sender = OTSender::new(b"m0".to_vec(), b"m1".to_vec())?;
+        let mut receiver = OTReceiver::new(Choice::One);
+        let public_keys = receiver.blind_choice(&sender.parameters())?;
+
+        let bytes = public_keys.to_bytes();
+        let decoded = ReceiverPublicKeys::from_bytes(&bytes)?;
+
+        assert_eq!(decoded.v, public_keys.v);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sender_response_round_trip() -> Result<()> {
+        let sender = OTSender::new(b"hello zero".to_vec(), b"hello one!".to_vec())?;
+        let mut receiver = OTReceiver::new(Choice::Zero);
+        let public_keys = receiver.blind_choice(&sender.parameters())?;
+        let response = sender.encrypt_messages(public_keys)?;
+
+        let bytes = response.to_bytes();
+        let decoded = SenderResponse::from_bytes(&bytes)?;
+
+        assert_eq!(decoded.masked_m0, response.masked_m0);
+        assert_eq!(decoded.masked_m1, response.masked_m1);
+        assert_eq!(decoded.signature, response.signature);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sender_response_round_trip_with_signature() -> Result<()> {
+        let signing_key = RsaPrivateKey::new(&mut rand::rngs::OsRng, 1024)?;
+        let sender = OTSender::new(b"hello zero".to_vec(), b"hello one!".to_vec())?
+            .with_signing_key(signing_key);
+        let mut receiver = OTReceiver::new(Choice::Zero);
+        let public_keys = receiver.blind_choice(&sender.parameters())?;
+        let response = sender.encrypt_messages(public_keys)?;
+
+        let bytes = response.to_bytes();
+        let decoded = SenderResponse::from_bytes(&bytes)?;
+
+        assert_eq!(decoded.signature, response.signature);
+        assert!(decoded.signature.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_protocol_over_wire_bytes() -> Result<()> {
+        let message0 = b"wire message zero".to_vec();
+        let message1 = b"wire message one!".to_vec();
+
+        let sender = OTSender::new(message0.clone(), message1.clone())?;
+        let mut receiver = OTReceiver::new(Choice::One);
+
+        // Sender -> receiver: parameters, as bytes only.
+        let params_bytes = sender.parameters().to_bytes()?;
+        let params = SenderParameters::from_bytes(&params_bytes)?;
+
+        // Receiver -> sender: blinded choice, as bytes only.
+        let public_keys_bytes = receiver.blind_choice(&params)?.to_bytes();
+        let public_keys = ReceiverPublicKeys::from_bytes(&public_keys_bytes)?;
+
+        // Sender -> receiver: masked messages, as bytes only.
+        let response_bytes = sender.encrypt_messages(public_keys)?.to_bytes();
+        let response = SenderResponse::from_bytes(&response_bytes)?;
+
+        let decrypted = receiver.decrypt_message(response)?;
+        assert_eq!(decrypted, message1);
+        Ok(())
+    }
+}
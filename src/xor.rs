@@ -1,23 +1,40 @@
 //! XOR operations module - blackboxed for security
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-/// Performs bitwise XOR on two byte arrays
-/// This function is intentionally separated to act as a blackbox operation
+/// Performs bitwise XOR on two byte arrays of equal length.
+/// This function is intentionally separated to act as a blackbox operation.
 pub fn xor(a: &[u8], b: &[u8]) -> Result<Vec<u8>> {
-    // Ensure both arrays have the same length by padding with zeros
-    let max_len = std::cmp::max(a.len(), b.len());
-    let mut a_padded = a.to_vec();
-    let mut b_padded = b.to_vec();
-    a_padded.resize(max_len, 0);
-    b_padded.resize(max_len, 0);
-
-    // Perform bitwise XOR operation
-    Ok(a_padded
-        .iter()
-        .zip(b_padded.iter())
-        .map(|(x, y)| x ^ y)
-        .collect())
+    if a.len() != b.len() {
+        return Err(anyhow!(
+            "xor operands must be the same length (got {} and {})",
+            a.len(),
+            b.len()
+        ));
+    }
+
+    Ok(a.iter().zip(b.iter()).map(|(x, y)| x ^ y).collect())
+}
+
+/// XOR-masks `data` against `pad`, using only `pad`'s first `data.len()`
+/// bytes. Masking is its own inverse, so the same function unmasks.
+///
+/// `pad` must be at least as long as `data`: a shorter pad would have to be
+/// zero-extended to XOR against the rest of `data`, and XOR-ing a byte with
+/// `0` is the identity, so every byte past the pad's length would come out
+/// of "masking" unchanged — i.e. in the clear. Callers must size the KDF
+/// output (`kdf::derive_key`'s `len` parameter) to at least `data.len()`.
+pub fn mask(data: &[u8], pad: &[u8]) -> Result<Vec<u8>> {
+    if pad.len() < data.len() {
+        return Err(anyhow!(
+            "mask pad ({} bytes) is shorter than data ({} bytes); bytes past the pad's \
+             length would leak unmasked",
+            pad.len(),
+            data.len()
+        ));
+    }
+
+    xor(data, &pad[..data.len()])
 }
 
 #[cfg(test)]
@@ -36,13 +53,45 @@ mod tests {
     }
 
     #[test]
-    fn test_xor_different_lengths() -> Result<()> {
+    fn test_xor_rejects_different_lengths() {
         let a = vec![0x12, 0x34];
         let b = vec![0xAB, 0xCD, 0xEF];
-        let expected = vec![0x12 ^ 0xAB, 0x34 ^ 0xCD, 0x00 ^ 0xEF];
 
-        let result = xor(&a, &b)?;
-        assert_eq!(result, expected);
+        assert!(xor(&a, &b).is_err());
+    }
+
+    #[test]
+    fn test_mask_roundtrip_with_longer_pad() -> Result<()> {
+        let data = b"hi".to_vec();
+        let pad = vec![0xFFu8; 32];
+
+        let masked = mask(&data, &pad)?;
+        assert_eq!(masked.len(), data.len());
+
+        let unmasked = mask(&masked, &pad)?;
+        assert_eq!(unmasked, data);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mask_rejects_pad_shorter_than_data() {
+        let data = vec![0x42u8; 100];
+        let pad = vec![0xFFu8; 32];
+
+        assert!(mask(&data, &pad).is_err());
+    }
+
+    #[test]
+    fn test_mask_does_not_leak_data_past_pad_length() -> Result<()> {
+        // A pad the exact length of `data` masks every byte; none of the
+        // original plaintext should survive unchanged (with overwhelming
+        // probability, since the pad is all non-zero bits here).
+        let data = vec![0x42u8; 100];
+        let pad = vec![0xFFu8; 100];
+
+        let masked = mask(&data, &pad)?;
+        assert_ne!(masked, data);
+        assert_eq!(masked, vec![!0x42u8; 100]);
         Ok(())
     }
 }